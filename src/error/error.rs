@@ -0,0 +1,52 @@
+use std::fmt;
+
+// The kind of problem encountered, independent of where it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    UnexpectedChar(char),
+    UnterminatedString,
+    ExpectedToken { expected: String, found: String },
+    TypeError(String),
+    UndeclaredVariable(String),
+    ParseError(String),
+    IntegerOverflow(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::UnexpectedChar(ch) => write!(f, "unexpected character '{}'", ch),
+            ErrorKind::UnterminatedString => write!(f, "unterminated string literal"),
+            ErrorKind::ExpectedToken { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            ErrorKind::TypeError(msg) => write!(f, "type error: {}", msg),
+            ErrorKind::UndeclaredVariable(name) => write!(f, "undeclared variable '{}'", name),
+            ErrorKind::ParseError(msg) => write!(f, "{}", msg),
+            ErrorKind::IntegerOverflow(digits) => {
+                write!(f, "integer literal '{}' is too large", digits)
+            }
+        }
+    }
+}
+
+// A problem found at a specific source location, reported by the lexer,
+// parser, or semantic analyzer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, line: usize, column: usize) -> Self {
+        Error { kind, line, column }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (line {}, column {})", self.kind, self.line, self.column)
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::lexer::lexer::Token;
+use crate::parser::parser::ASTNode;
+use crate::runtime::runtime::{Runtime, Scope, Value};
+
+// Errors raised while evaluating an already type-checked AST.
+#[derive(Debug, Clone)]
+pub enum RuntimeError {
+    TypeError(String),
+    UndeclaredVariable(String),
+    UnsupportedOperation(String),
+    // Not a real error: unwinds the call stack up to the enclosing `Call`,
+    // carrying the returned value with it.
+    Return(Value),
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuntimeError::TypeError(msg) => write!(f, "Type error: {}", msg),
+            RuntimeError::UndeclaredVariable(name) => write!(f, "Undeclared variable: {}", name),
+            RuntimeError::UnsupportedOperation(msg) => write!(f, "Unsupported operation: {}", msg),
+            RuntimeError::Return(_) => write!(f, "'return' used outside of a function"),
+        }
+    }
+}
+
+// Tree-walking evaluator: walks the AST produced by `Parser` and executes it
+// against a `Runtime`, producing a `Value`.
+pub struct Interpreter {
+    runtime: Runtime,
+    // Name, params, body, and the scope chain captured at declaration time
+    // (the function's closure), so a call resolves lexically-scoped
+    // variables against its defining environment rather than whatever is on
+    // the stack at the call site.
+    functions: HashMap<String, (Vec<String>, ASTNode, Vec<Scope>)>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter {
+            runtime: Runtime::new(),
+            functions: HashMap::new(),
+        }
+    }
+
+    pub fn eval(&mut self, node: &ASTNode) -> Result<Value, RuntimeError> {
+        match node {
+            ASTNode::Program(stmts) | ASTNode::StmtList(stmts) => self.eval_stmts(stmts),
+            ASTNode::Block(stmts) => {
+                self.runtime.enter_scope();
+                let result = self.eval_stmts(stmts);
+                self.runtime.exit_scope();
+                result
+            }
+            ASTNode::ExprStmt(expr) => self.eval(expr),
+            ASTNode::LetStmt(name, expr, _, _) => {
+                let value = self.eval(expr)?;
+                if self.runtime.in_local_scope() {
+                    self.runtime.set_local(name.clone(), value.clone());
+                } else {
+                    self.runtime.set_global(name.clone(), value.clone());
+                }
+                Ok(value)
+            }
+            ASTNode::Identifier(name, depth, _, _) => self.lookup(name, *depth),
+            ASTNode::Integer(value) => Ok(Value::Integer(*value)),
+            ASTNode::StringLiteral(value) => Ok(Value::Str(value.clone())),
+            ASTNode::BooleanLiteral(value) => Ok(Value::Boolean(*value)),
+            ASTNode::NilLiteral => Ok(Value::Nil),
+            ASTNode::UnaryOp(op, operand, _, _) => {
+                let value = self.eval(operand)?;
+                match (op, value) {
+                    (Token::Bang, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+                    (Token::Minus, Value::Integer(i)) => Ok(Value::Integer(-i)),
+                    (op, value) => Err(RuntimeError::TypeError(format!(
+                        "cannot apply {:?} to {:?}",
+                        op, value
+                    ))),
+                }
+            }
+            ASTNode::BinaryOp(left, op, right, _, _) => {
+                let left_val = self.eval(left)?;
+                let right_val = self.eval(right)?;
+                Self::eval_binary_op(left_val, op, right_val)
+            }
+            ASTNode::LogicalOp(left, op, right, _, _) => {
+                let left_val = self.eval(left)?;
+                let left_bool = Self::as_bool(&left_val)?;
+                match (op, left_bool) {
+                    (Token::And, false) => Ok(left_val),
+                    (Token::And, true) => self.eval(right),
+                    (Token::Or, true) => Ok(left_val),
+                    (Token::Or, false) => self.eval(right),
+                    _ => Err(RuntimeError::UnsupportedOperation(format!(
+                        "{:?} is not a logical operator",
+                        op
+                    ))),
+                }
+            }
+            ASTNode::IfStmt(condition, then_branch, else_branch, _, _) => {
+                if self.eval_condition(condition)? {
+                    self.eval(then_branch)
+                } else if let Some(else_branch) = else_branch {
+                    self.eval(else_branch)
+                } else {
+                    Ok(Value::Boolean(false))
+                }
+            }
+            ASTNode::WhileStmt(condition, body, _, _) => {
+                let mut result = Value::Boolean(false);
+                while self.eval_condition(condition)? {
+                    result = self.eval(body)?;
+                }
+                Ok(result)
+            }
+            ASTNode::ReturnStmt(expr, _, _) => {
+                let value = self.eval(expr)?;
+                Err(RuntimeError::Return(value))
+            }
+            ASTNode::FnDecl(name, params, body, _, _) => {
+                let param_names = params.iter().map(|(name, _)| name.clone()).collect();
+                let closure = self.runtime.capture_scope();
+                self.functions.insert(name.clone(), (param_names, (**body).clone(), closure));
+                Ok(Value::Boolean(true))
+            }
+            ASTNode::Call(callee, args, _, _) => {
+                let name = match &**callee {
+                    ASTNode::Identifier(name, _, _, _) => name.clone(),
+                    _ => {
+                        return Err(RuntimeError::UnsupportedOperation(
+                            "can only call named functions".to_string(),
+                        ))
+                    }
+                };
+                let (params, body, closure) = self
+                    .functions
+                    .get(&name)
+                    .cloned()
+                    .ok_or_else(|| RuntimeError::UndeclaredVariable(name.clone()))?;
+
+                let mut arg_values = Vec::with_capacity(args.len());
+                for arg in args {
+                    arg_values.push(self.eval(arg)?);
+                }
+
+                let caller_scope = self.runtime.swap_scope(closure);
+                self.runtime.enter_scope();
+                for (param, value) in params.into_iter().zip(arg_values) {
+                    self.runtime.set_local(param, value);
+                }
+                let result = self.eval(&body);
+                self.runtime.exit_scope();
+                self.runtime.swap_scope(caller_scope);
+                match result {
+                    Err(RuntimeError::Return(value)) => Ok(value),
+                    other => other,
+                }
+            }
+        }
+    }
+
+    fn eval_stmts(&mut self, stmts: &[ASTNode]) -> Result<Value, RuntimeError> {
+        let mut result = Value::Boolean(false);
+        for stmt in stmts {
+            result = self.eval(stmt)?;
+        }
+        Ok(result)
+    }
+
+    fn eval_condition(&mut self, node: &ASTNode) -> Result<bool, RuntimeError> {
+        let value = self.eval(node)?;
+        Self::as_bool(&value)
+    }
+
+    fn as_bool(value: &Value) -> Result<bool, RuntimeError> {
+        match value {
+            Value::Boolean(b) => Ok(*b),
+            other => Err(RuntimeError::TypeError(format!(
+                "expected a boolean, found {:?}",
+                other
+            ))),
+        }
+    }
+
+    // `depth` is the number of scopes to climb, as computed by `Resolver`;
+    // when absent (e.g. an identifier the resolver never visited) fall back
+    // to the old local-then-global search.
+    fn lookup(&self, name: &str, depth: Option<usize>) -> Result<Value, RuntimeError> {
+        let value = match depth {
+            Some(depth) => self.runtime.get_at_depth(depth, name),
+            None => self.runtime.get_local(name).or_else(|| self.runtime.get_global(name)),
+        };
+        value.ok_or_else(|| RuntimeError::UndeclaredVariable(name.to_string()))
+    }
+
+    fn eval_binary_op(left: Value, op: &Token, right: Value) -> Result<Value, RuntimeError> {
+        match (left, op, right) {
+            (Value::Integer(l), Token::Plus, Value::Integer(r)) => Ok(Value::Integer(l + r)),
+            (Value::Integer(l), Token::Minus, Value::Integer(r)) => Ok(Value::Integer(l - r)),
+            (Value::Integer(l), Token::Star, Value::Integer(r)) => Ok(Value::Integer(l * r)),
+            (Value::Integer(l), Token::Slash, Value::Integer(r)) => {
+                if r == 0 {
+                    Err(RuntimeError::UnsupportedOperation("division by zero".to_string()))
+                } else {
+                    Ok(Value::Integer(l / r))
+                }
+            }
+            (Value::Str(l), Token::Plus, Value::Str(r)) => Ok(Value::Str(l + &r)),
+            (Value::Integer(l), Token::Equal, Value::Integer(r)) => Ok(Value::Boolean(l == r)),
+            (Value::Integer(l), Token::NotEqual, Value::Integer(r)) => Ok(Value::Boolean(l != r)),
+            (Value::Integer(l), Token::LessThan, Value::Integer(r)) => Ok(Value::Boolean(l < r)),
+            (Value::Integer(l), Token::GreaterThan, Value::Integer(r)) => Ok(Value::Boolean(l > r)),
+            (Value::Integer(l), Token::LessEqual, Value::Integer(r)) => Ok(Value::Boolean(l <= r)),
+            (Value::Integer(l), Token::GreaterEqual, Value::Integer(r)) => Ok(Value::Boolean(l >= r)),
+            (Value::Str(l), Token::Equal, Value::Str(r)) => Ok(Value::Boolean(l == r)),
+            (Value::Str(l), Token::NotEqual, Value::Str(r)) => Ok(Value::Boolean(l != r)),
+            (Value::Boolean(l), Token::Equal, Value::Boolean(r)) => Ok(Value::Boolean(l == r)),
+            (Value::Boolean(l), Token::NotEqual, Value::Boolean(r)) => Ok(Value::Boolean(l != r)),
+            (Value::Nil, Token::Equal, Value::Nil) => Ok(Value::Boolean(true)),
+            (Value::Nil, Token::NotEqual, Value::Nil) => Ok(Value::Boolean(false)),
+            (l, op, r) => Err(RuntimeError::TypeError(format!(
+                "cannot apply {:?} to {:?} and {:?}",
+                op, l, r
+            ))),
+        }
+    }
+}
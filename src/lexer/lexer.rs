@@ -1,3 +1,5 @@
+use crate::error::error::{Error, ErrorKind};
+
 #[derive(Debug, PartialEq,Clone)]
 pub enum Token{
 	
@@ -8,6 +10,11 @@ pub enum Token{
 	Else,
 	While,
 	Return,
+	And,
+	Or,
+	True,
+	False,
+	Nil,
 
 
 	// Identifiers
@@ -22,10 +29,14 @@ pub enum Token{
     Minus,
     Star,
     Slash,
+    Assign,
     Equal,
     NotEqual,
+    Bang,
     LessThan,
     GreaterThan,
+    LessEqual,
+    GreaterEqual,
 
     // Delimiters
     LParen,
@@ -34,17 +45,31 @@ pub enum Token{
     RBrace,
     Comma,
     Semicolon,
+    Colon,
 
     // End of file
     EOF,
 }
 
 
+// A token paired with the line/column where it starts, so later stages can
+// report errors against the original source.
+#[derive(Debug, Clone)]
+pub struct PositionedToken {
+    pub token: Token,
+    pub line: usize,
+    pub column: usize,
+}
+
 //Now we define out input string
 pub struct Lexer {
 	input: String,
 	position: usize,
 	current_char: Option<char>,
+	line: usize,
+	column: usize,
+	token_line: usize,
+	token_column: usize,
 }
 
 
@@ -55,12 +80,25 @@ impl Lexer {
             input,
             position: 0,
             current_char: None,
+            line: 1,
+            column: 1,
+            token_line: 1,
+            token_column: 1,
         };
         lexer.advance(); // Initialize by advancing to the first character
         lexer
     }
 
     fn advance(&mut self) {
+        if let Some(ch) = self.current_char {
+            if ch == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
         if self.position < self.input.len() {
             self.current_char = Some(self.input.chars().nth(self.position).unwrap());
             self.position += 1;
@@ -68,12 +106,33 @@ impl Lexer {
             self.current_char = None; // End of input
         }
     }
+
+    // Looks at the character after `current_char` without consuming anything.
+    fn peek(&self) -> Option<char> {
+        self.input.chars().nth(self.position)
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind, self.token_line, self.token_column)
+    }
 }
 
 // Second impl block for tokenization methods
 impl Lexer {
-    pub fn get_next_token(&mut self) -> Token {
+    // Tokenizes the next token along with the position it starts at.
+    pub fn next_token(&mut self) -> Result<PositionedToken, Error> {
+        let token = self.get_next_token()?;
+        Ok(PositionedToken {
+            token,
+            line: self.token_line,
+            column: self.token_column,
+        })
+    }
+
+    pub fn get_next_token(&mut self) -> Result<Token, Error> {
         while let Some(ch) = self.current_char {
+            self.token_line = self.line;
+            self.token_column = self.column;
             match ch {
                 // Skip whitespace
                 ' ' | '\t' | '\n' | '\r' => self.advance(), 
@@ -81,63 +140,87 @@ impl Lexer {
                 // Operators
                 '+' => {
                     self.advance();
-                    return Token::Plus;
+                    return Ok(Token::Plus);
                 },
                 '-' => {
                     self.advance();
-                    return Token::Minus;
+                    return Ok(Token::Minus);
                 },
                 '*' => {
                     self.advance();
-                    return Token::Star;
+                    return Ok(Token::Star);
                 },
                 '/' => {
                     self.advance();
-                    return Token::Slash;
+                    return Ok(Token::Slash);
                 },
                 '=' => {
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        self.advance();
+                        return Ok(Token::Equal);
+                    }
                     self.advance();
-                    return Token::Equal;
+                    return Ok(Token::Assign);
                 },
 
                 '>' => {
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        self.advance();
+                        return Ok(Token::GreaterEqual);
+                    }
                     self.advance();
-                    return Token::GreaterThan
+                    return Ok(Token::GreaterThan);
                 },
                 '<' => {
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        self.advance();
+                        return Ok(Token::LessEqual);
+                    }
                     self.advance();
-                    return Token::LessThan
+                    return Ok(Token::LessThan);
                 },
 
                 '!' => {
+                    if self.peek() == Some('=') {
+                        self.advance();
+                        self.advance();
+                        return Ok(Token::NotEqual);
+                    }
                     self.advance();
-                    return Token::NotEqual
+                    return Ok(Token::Bang);
                 },
 
                 // Delimiters
                 '(' => {
                     self.advance();
-                    return Token::LParen;
+                    return Ok(Token::LParen);
                 },
                 ')' => {
                     self.advance();
-                    return Token::RParen;
+                    return Ok(Token::RParen);
                 },
                 '{' => {
                     self.advance();
-                    return Token::LBrace;
+                    return Ok(Token::LBrace);
                 },
                 '}' => {
                     self.advance();
-                    return Token::RBrace;
+                    return Ok(Token::RBrace);
                 },
                 ',' => {
                     self.advance();
-                    return Token::Comma;
+                    return Ok(Token::Comma);
                 },
                 ';' => {
                     self.advance();
-                    return Token::Semicolon;
+                    return Ok(Token::Semicolon);
+                },
+                ':' => {
+                    self.advance();
+                    return Ok(Token::Colon);
                 },
 
                 // String literals
@@ -152,17 +235,17 @@ impl Lexer {
 
                 // Identifiers and keywords
                 'a'..='z' | 'A'..='Z' | '_' => {
-                    return self.identifier();
+                    return Ok(self.identifier());
                 },
 
                 // Handle unexpected characters
                 _ => {
-                    panic!("Unexpected character: {}", ch);
+                    return Err(self.error(ErrorKind::UnexpectedChar(ch)));
                 }
             }
         }
 
-        Token::EOF
+        Ok(Token::EOF)
     }
 
     fn identifier(&mut self) -> Token {
@@ -184,11 +267,16 @@ impl Lexer {
             "else" => Token::Else,
             "while" => Token::While,
             "return" => Token::Return,
+            "and" => Token::And,
+            "or" => Token::Or,
+            "true" => Token::True,
+            "false" => Token::False,
+            "nil" => Token::Nil,
             _ => Token::Identifier(result),
         }
     }
 
-    fn integer_literal(&mut self) -> Token {
+    fn integer_literal(&mut self) -> Result<Token, Error> {
         let mut result = String::new();
 
         while let Some(ch) = self.current_char {
@@ -200,24 +288,27 @@ impl Lexer {
             }
         }
 
-        Token::Integer(result.parse::<i64>().unwrap())
+        result
+            .parse::<i64>()
+            .map(Token::Integer)
+            .map_err(|_| self.error(ErrorKind::IntegerOverflow(result)))
     }
 
-    fn string_literal(&mut self) -> Token {
+    fn string_literal(&mut self) -> Result<Token, Error> {
         let mut result = String::new();
         self.advance(); // Skip the opening quote
 
         while let Some(ch) = self.current_char {
             if ch == '"' {
                 self.advance(); // Skip the closing quote
-                return Token::StringLiteral(result);
+                return Ok(Token::StringLiteral(result));
             } else {
                 result.push(ch);
                 self.advance();
             }
         }
 
-        panic!("Unterminated string literal");
+        Err(self.error(ErrorKind::UnterminatedString))
     }
 }
 
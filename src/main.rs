@@ -34,13 +34,19 @@
 //     }
 // }
 
+mod error;
 mod lexer;
 mod parser;
+mod resolver;
 mod typecheck;
+mod runtime;
+mod interpreter;
 
 use lexer::lexer::{Lexer, Token};
 use parser::parser::{Parser};
+use resolver::resolver::Resolver;
 use typecheck::typecheck::{SemanticAnalyzer};
+use interpreter::interpreter::Interpreter;
 
 fn main() {
     // Example TinyScript program
@@ -48,7 +54,7 @@ fn main() {
         let x = 42;
         if (x > 10) {
             let y = x + 5;
-            print(y);
+            y;
         }
     "#);
 
@@ -56,28 +62,55 @@ fn main() {
     let mut lexer = Lexer::new(input);
     let mut tokens = Vec::new();
 
-//     // Collect tokens from the lexer
-     loop {
-        let token = lexer.get_next_token();
-         if token == Token::EOF {
-                      break;
-         }
-         tokens.push(token);
+    // Collect tokens from the lexer
+    loop {
+        let positioned = match lexer.next_token() {
+            Ok(positioned) => positioned,
+            Err(e) => {
+                println!("Lexer error: {}", e);
+                return;
+            }
+        };
+        let is_eof = positioned.token == Token::EOF;
+        tokens.push(positioned);
+        if is_eof {
+            break;
+        }
     } // Collect all tokens into a vector
 
     // Parsing: Convert tokens into an Abstract Syntax Tree (AST)
     let mut parser = Parser::new(tokens);
-    let ast = parser.parse_program().expect("Parsing failed");
+    let mut ast = match parser.parse_program() {
+        Ok(ast) => ast,
+        Err(e) => {
+            println!("Parse error: {}", e);
+            return;
+        }
+    };
 
-    // Semantic Analysis: Type-checking and scope resolution
+    // Resolution: annotate each identifier with the number of scopes to
+    // climb to reach its declaration
+    let mut resolver = Resolver::new();
+    if let Err(e) = resolver.resolve(&mut ast) {
+        println!("Resolution error: {}", e);
+        return;
+    }
+
+    // Semantic Analysis: Type-checking
     let mut analyzer = SemanticAnalyzer::new();
     if let Err(e) = analyzer.check_types(&ast) {
         println!("Semantic error: {}", e);
-    } else {
-        println!("Semantic analysis passed");
+        return;
+    }
+    println!("Semantic analysis passed");
+
+    // Evaluation: walk the AST and execute the program
+    let mut interpreter = Interpreter::new();
+    match interpreter.eval(&ast) {
+        Ok(value) => println!("Result: {:?}", value),
+        Err(e) => println!("Runtime error: {}", e),
     }
 
-    // If everything passes, you can proceed with code generation or execution
     println!("{:#?}", ast);
 }
 
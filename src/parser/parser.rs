@@ -1,28 +1,36 @@
-use crate::lexer::lexer::Token; 
+use crate::error::error::{Error, ErrorKind};
+use crate::lexer::lexer::{PositionedToken, Token};
+use crate::typecheck::typecheck::Type;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ASTNode {
     Program(Vec<ASTNode>),          // The entire program (a list of statements)
     StmtList(Vec<ASTNode>),         // A list of statements
-    LetStmt(String, Box<ASTNode>),  // let statement: variable name and expression
-    IfStmt(Box<ASTNode>, Box<ASTNode>, Option<Box<ASTNode>>), // if condition, then block, else block (optional)
-    WhileStmt(Box<ASTNode>, Box<ASTNode>),  // while loop: condition and body
-    ReturnStmt(Box<ASTNode>),       // return statement: expression
+    LetStmt(String, Box<ASTNode>, usize, usize),  // let statement: variable name, expression, line, column
+    IfStmt(Box<ASTNode>, Box<ASTNode>, Option<Box<ASTNode>>, usize, usize), // if condition, then block, else block (optional), line, column
+    WhileStmt(Box<ASTNode>, Box<ASTNode>, usize, usize),  // while loop: condition, body, line, column
+    ReturnStmt(Box<ASTNode>, usize, usize),       // return statement: expression, line, column
     Block(Vec<ASTNode>),            // Block of statements
     ExprStmt(Box<ASTNode>),         // Expression statement
-    BinaryOp(Box<ASTNode>, Token, Box<ASTNode>),  // Binary operation: left operand, operator, right operand
-    Identifier(String),             // Identifier (variable name)
+    BinaryOp(Box<ASTNode>, Token, Box<ASTNode>, usize, usize),  // Binary operation: left operand, operator, right operand, line, column
+    LogicalOp(Box<ASTNode>, Token, Box<ASTNode>, usize, usize), // Logical and/or: left operand, operator, right operand (short-circuits), line, column
+    FnDecl(String, Vec<(String, Type)>, Box<ASTNode>, usize, usize), // function declaration: name, typed params, body, line, column
+    Call(Box<ASTNode>, Vec<ASTNode>, usize, usize),                  // call expression: callee, arguments, line, column
+    Identifier(String, Option<usize>, usize, usize), // Identifier (name, scope depth resolved by `Resolver`, line, column)
     Integer(i64),                   // Integer literal
     StringLiteral(String),          // String literal
+    BooleanLiteral(bool),           // Boolean literal: true / false
+    NilLiteral,                     // nil literal
+    UnaryOp(Token, Box<ASTNode>, usize, usize),   // Unary operation: operator, operand, line, column
 }
 
 pub struct Parser {
-    tokens: Vec<Token>,  // List of tokens from the lexer
+    tokens: Vec<PositionedToken>,  // List of tokens from the lexer, with source positions
     current_token: usize,  // Current token position
 }
 
 impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Self {
+    pub fn new(tokens: Vec<PositionedToken>) -> Self {
         Parser {
             tokens,
             current_token: 0,
@@ -37,26 +45,51 @@ impl Parser {
 
     fn current(&self) -> &Token {
         if self.current_token < self.tokens.len() {
-            &self.tokens[self.current_token]
+            &self.tokens[self.current_token].token
         } else {
             &Token::EOF // Return EOF if we've gone past the end
         }
     }
 
-    fn expect(&mut self, expected: Token) -> Result<(), String> {
+    // Position of the current token, for error reporting.
+    fn current_pos(&self) -> (usize, usize) {
+        if self.current_token < self.tokens.len() {
+            let positioned = &self.tokens[self.current_token];
+            (positioned.line, positioned.column)
+        } else if let Some(last) = self.tokens.last() {
+            (last.line, last.column)
+        } else {
+            (1, 1)
+        }
+    }
+
+    fn parse_error(&self, message: impl Into<String>) -> Error {
+        let (line, column) = self.current_pos();
+        Error::new(ErrorKind::ParseError(message.into()), line, column)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), Error> {
         if *self.current() == expected {
             self.advance();
             Ok(())
         } else {
-            Err(format!("Expected {:?}, found {:?}", expected, self.current()))
+            let (line, column) = self.current_pos();
+            Err(Error::new(
+                ErrorKind::ExpectedToken {
+                    expected: format!("{:?}", expected),
+                    found: format!("{:?}", self.current()),
+                },
+                line,
+                column,
+            ))
         }
     }
 
-    pub fn parse_program(&mut self) -> Result<ASTNode, String> {
+    pub fn parse_program(&mut self) -> Result<ASTNode, Error> {
         self.parse_stmt_list()
     }
 
-    fn parse_stmt_list(&mut self) -> Result<ASTNode, String> {
+    fn parse_stmt_list(&mut self) -> Result<ASTNode, Error> {
         let mut stmts = Vec::new();
         while self.current() != &Token::EOF && self.current() != &Token::RBrace {
             stmts.push(self.parse_stmt()?);
@@ -64,35 +97,90 @@ impl Parser {
         Ok(ASTNode::StmtList(stmts))
     }
 
-    fn parse_stmt(&mut self) -> Result<ASTNode, String> {
+    fn parse_stmt(&mut self) -> Result<ASTNode, Error> {
         match self.current() {
             Token::Let => self.parse_let_stmt(),
             Token::If => self.parse_if_stmt(),
             Token::While => self.parse_while_stmt(),
             Token::Return => self.parse_return_stmt(),
+            Token::Fn => self.parse_fn_decl(),
             Token::LBrace => self.parse_block(),
             _ => self.parse_expr_stmt(),
         }
     }
 
-    fn parse_let_stmt(&mut self) -> Result<ASTNode, String> {
+    fn parse_type(&mut self) -> Result<Type, Error> {
+        let name = if let Token::Identifier(name) = self.current() {
+            name.clone()
+        } else {
+            return Err(self.parse_error("Expected type name"));
+        };
+        self.advance();
+        match name.as_str() {
+            "int" => Ok(Type::Integer),
+            "string" => Ok(Type::String),
+            "bool" => Ok(Type::Boolean),
+            _ => Err(self.parse_error(format!("Unknown type: {}", name))),
+        }
+    }
+
+    fn parse_fn_decl(&mut self) -> Result<ASTNode, Error> {
+        let (line, column) = self.current_pos();
+        self.expect(Token::Fn)?;
+        let name = if let Token::Identifier(name) = self.current() {
+            name.clone()
+        } else {
+            return Err(self.parse_error("Expected function name"));
+        };
+        self.advance();
+
+        self.expect(Token::LParen)?;
+        let mut params = Vec::new();
+        if self.current() != &Token::RParen {
+            loop {
+                let param_name = if let Token::Identifier(name) = self.current() {
+                    name.clone()
+                } else {
+                    return Err(self.parse_error("Expected parameter name"));
+                };
+                self.advance();
+                self.expect(Token::Colon)?;
+                let param_type = self.parse_type()?;
+                params.push((param_name, param_type));
+
+                if self.current() == &Token::Comma {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+        }
+        self.expect(Token::RParen)?;
+
+        let body = self.parse_block()?;
+        Ok(ASTNode::FnDecl(name, params, Box::new(body), line, column))
+    }
+
+    fn parse_let_stmt(&mut self) -> Result<ASTNode, Error> {
+        let (line, column) = self.current_pos();
         self.expect(Token::Let)?;
         let identifier = if let Token::Identifier(name) = self.current() {
             name.clone()
         } else {
-            return Err("Expected identifier".to_string());
+            return Err(self.parse_error("Expected identifier"));
         };
         self.advance();
-        self.expect(Token::Equal)?;
-        let expr = self.parse_expr()?;
+        self.expect(Token::Assign)?;
+        let expr = self.parse_or()?;
         self.expect(Token::Semicolon)?;
-        Ok(ASTNode::LetStmt(identifier, Box::new(expr)))
+        Ok(ASTNode::LetStmt(identifier, Box::new(expr), line, column))
     }
 
-    fn parse_if_stmt(&mut self) -> Result<ASTNode, String> {
+    fn parse_if_stmt(&mut self) -> Result<ASTNode, Error> {
+        let (line, column) = self.current_pos();
         self.expect(Token::If)?;
         self.expect(Token::LParen)?;
-        let condition = self.parse_expr()?;
+        let condition = self.parse_or()?;
         self.expect(Token::RParen)?;
         let then_branch = self.parse_stmt()?;
         let else_branch = if self.current() == &Token::Else {
@@ -101,26 +189,28 @@ impl Parser {
         } else {
             None
         };
-        Ok(ASTNode::IfStmt(Box::new(condition), Box::new(then_branch), else_branch.map(Box::new)))
+        Ok(ASTNode::IfStmt(Box::new(condition), Box::new(then_branch), else_branch.map(Box::new), line, column))
     }
 
-    fn parse_while_stmt(&mut self) -> Result<ASTNode, String> {
+    fn parse_while_stmt(&mut self) -> Result<ASTNode, Error> {
+        let (line, column) = self.current_pos();
         self.expect(Token::While)?;
         self.expect(Token::LParen)?;
-        let condition = self.parse_expr()?;
+        let condition = self.parse_or()?;
         self.expect(Token::RParen)?;
         let body = self.parse_stmt()?;
-        Ok(ASTNode::WhileStmt(Box::new(condition), Box::new(body)))
+        Ok(ASTNode::WhileStmt(Box::new(condition), Box::new(body), line, column))
     }
 
-    fn parse_return_stmt(&mut self) -> Result<ASTNode, String> {
+    fn parse_return_stmt(&mut self) -> Result<ASTNode, Error> {
+        let (line, column) = self.current_pos();
         self.expect(Token::Return)?;
-        let expr = self.parse_expr()?;
+        let expr = self.parse_or()?;
         self.expect(Token::Semicolon)?;
-        Ok(ASTNode::ReturnStmt(Box::new(expr)))
+        Ok(ASTNode::ReturnStmt(Box::new(expr), line, column))
     }
 
-    fn parse_block(&mut self) -> Result<ASTNode, String> {
+    fn parse_block(&mut self) -> Result<ASTNode, Error> {
         self.expect(Token::LBrace)?;
         let mut stmts = Vec::new();
         while self.current() != &Token::RBrace {
@@ -130,46 +220,97 @@ impl Parser {
         Ok(ASTNode::Block(stmts))
     }
 
-    fn parse_expr_stmt(&mut self) -> Result<ASTNode, String> {
-        let expr = self.parse_expr()?;
+    fn parse_expr_stmt(&mut self) -> Result<ASTNode, Error> {
+        let expr = self.parse_or()?;
         self.expect(Token::Semicolon)?;
         Ok(ASTNode::ExprStmt(Box::new(expr)))
     }
 
-    fn parse_expr(&mut self) -> Result<ASTNode, String> {
+    fn parse_or(&mut self) -> Result<ASTNode, Error> {
+        let mut node = self.parse_and()?;
+        while self.current() == &Token::Or {
+            let (line, column) = self.current_pos();
+            let op = self.current().clone();
+            self.advance();
+            let right = self.parse_and()?;
+            node = ASTNode::LogicalOp(Box::new(node), op, Box::new(right), line, column);
+        }
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<ASTNode, Error> {
+        let mut node = self.parse_expr()?;
+        while self.current() == &Token::And {
+            let (line, column) = self.current_pos();
+            let op = self.current().clone();
+            self.advance();
+            let right = self.parse_expr()?;
+            node = ASTNode::LogicalOp(Box::new(node), op, Box::new(right), line, column);
+        }
+        Ok(node)
+    }
+
+    fn parse_expr(&mut self) -> Result<ASTNode, Error> {
         let mut node = self.parse_term()?;
-        while let Token::Plus | Token::Minus | Token::GreaterThan | Token::LessThan | Token::Equal | Token::NotEqual = self.current() {
+        while let Token::Plus
+        | Token::Minus
+        | Token::GreaterThan
+        | Token::LessThan
+        | Token::GreaterEqual
+        | Token::LessEqual
+        | Token::Equal
+        | Token::NotEqual = self.current() {
+            let (line, column) = self.current_pos();
             let op = self.current().clone();
             self.advance();
             let right = self.parse_term()?;
-            node = ASTNode::BinaryOp(Box::new(node), op, Box::new(right));
+            node = ASTNode::BinaryOp(Box::new(node), op, Box::new(right), line, column);
         }
         Ok(node)
     }
 
-    fn parse_term(&mut self) -> Result<ASTNode, String> {
+    fn parse_term(&mut self) -> Result<ASTNode, Error> {
         let mut node = self.parse_factor()?;
         while let Token::Star | Token::Slash = self.current() {
+            let (line, column) = self.current_pos();
             let op = self.current().clone();
             self.advance();
             let right = self.parse_factor()?;
-            node = ASTNode::BinaryOp(Box::new(node), op, Box::new(right));
+            node = ASTNode::BinaryOp(Box::new(node), op, Box::new(right), line, column);
         }
         Ok(node)
     }
 
-    fn parse_factor(&mut self) -> Result<ASTNode, String> {
+    fn parse_factor(&mut self) -> Result<ASTNode, Error> {
         match self.current() {
             Token::LParen => {
                 self.advance();
-                let node = self.parse_expr()?;
+                let node = self.parse_or()?;
                 self.expect(Token::RParen)?;
                 Ok(node)
             }
             Token::Identifier(name) => {
-                let node = ASTNode::Identifier(name.clone());
+                let name = name.clone();
+                let (line, column) = self.current_pos();
                 self.advance();
-                Ok(node)
+                if self.current() == &Token::LParen {
+                    self.advance();
+                    let mut args = Vec::new();
+                    if self.current() != &Token::RParen {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if self.current() == &Token::Comma {
+                                self.advance();
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    Ok(ASTNode::Call(Box::new(ASTNode::Identifier(name, None, line, column)), args, line, column))
+                } else {
+                    Ok(ASTNode::Identifier(name, None, line, column))
+                }
             }
             Token::Integer(value) => {
                 let node = ASTNode::Integer(*value);
@@ -181,7 +322,26 @@ impl Parser {
                 self.advance();
                 Ok(node)
             }
-            _ => Err("Unexpected token in factor".to_string()),
+            Token::True => {
+                self.advance();
+                Ok(ASTNode::BooleanLiteral(true))
+            }
+            Token::False => {
+                self.advance();
+                Ok(ASTNode::BooleanLiteral(false))
+            }
+            Token::Nil => {
+                self.advance();
+                Ok(ASTNode::NilLiteral)
+            }
+            Token::Minus | Token::Bang => {
+                let (line, column) = self.current_pos();
+                let op = self.current().clone();
+                self.advance();
+                let operand = self.parse_factor()?;
+                Ok(ASTNode::UnaryOp(op, Box::new(operand), line, column))
+            }
+            _ => Err(self.parse_error("Unexpected token in factor")),
         }
     }
 }
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::error::error::{Error, ErrorKind};
+use crate::parser::parser::ASTNode;
+
+// Walks the AST once after parsing and annotates every `Identifier` with how
+// many enclosing scopes to climb to find its declaration, so the evaluator
+// can index straight into its scope stack instead of searching it at every
+// use. Also catches use-before-declaration and duplicate declarations.
+pub struct Resolver {
+    scopes: Vec<HashMap<String, bool>>,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Resolver {
+            scopes: vec![HashMap::new()], // the global scope
+        }
+    }
+
+    pub fn resolve(&mut self, node: &mut ASTNode) -> Result<(), Error> {
+        match node {
+            ASTNode::Program(stmts) | ASTNode::StmtList(stmts) => {
+                for stmt in stmts {
+                    self.resolve(stmt)?;
+                }
+                Ok(())
+            }
+            ASTNode::Block(stmts) => {
+                self.enter_scope();
+                for stmt in stmts {
+                    self.resolve(stmt)?;
+                }
+                self.exit_scope();
+                Ok(())
+            }
+            ASTNode::ExprStmt(expr) => self.resolve(expr),
+            ASTNode::ReturnStmt(expr, _, _) => self.resolve(expr),
+            ASTNode::LetStmt(name, expr, line, column) => {
+                self.resolve(expr)?;
+                self.declare(name, *line, *column)
+            }
+            ASTNode::IfStmt(condition, then_branch, else_branch, _, _) => {
+                self.resolve(condition)?;
+                self.resolve(then_branch)?;
+                if let Some(else_branch) = else_branch {
+                    self.resolve(else_branch)?;
+                }
+                Ok(())
+            }
+            ASTNode::WhileStmt(condition, body, _, _) => {
+                self.resolve(condition)?;
+                self.resolve(body)
+            }
+            ASTNode::BinaryOp(left, _, right, _, _) | ASTNode::LogicalOp(left, _, right, _, _) => {
+                self.resolve(left)?;
+                self.resolve(right)
+            }
+            ASTNode::FnDecl(name, params, body, line, column) => {
+                self.declare(name, *line, *column)?;
+                self.enter_scope();
+                for (param_name, _) in params.iter() {
+                    self.declare(param_name, *line, *column)?;
+                }
+                self.resolve(body)?;
+                self.exit_scope();
+                Ok(())
+            }
+            ASTNode::Call(callee, args, _, _) => {
+                self.resolve(callee)?;
+                for arg in args {
+                    self.resolve(arg)?;
+                }
+                Ok(())
+            }
+            ASTNode::Identifier(name, depth, line, column) => {
+                *depth = Some(self.resolve_depth(name, *line, *column)?);
+                Ok(())
+            }
+            ASTNode::UnaryOp(_, operand, _, _) => self.resolve(operand),
+            ASTNode::Integer(_) | ASTNode::StringLiteral(_) | ASTNode::BooleanLiteral(_) | ASTNode::NilLiteral => Ok(()),
+        }
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: &str, line: usize, column: usize) -> Result<(), Error> {
+        let scope = self.scopes.last_mut().unwrap();
+        if scope.contains_key(name) {
+            return Err(Error::new(
+                ErrorKind::TypeError(format!("'{}' is already declared in this scope", name)),
+                line,
+                column,
+            ));
+        }
+        scope.insert(name.to_string(), true);
+        Ok(())
+    }
+
+    fn resolve_depth(&self, name: &str, line: usize, column: usize) -> Result<usize, Error> {
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(name) {
+                return Ok(depth);
+            }
+        }
+        Err(Error::new(ErrorKind::UndeclaredVariable(name.to_string()), line, column))
+    }
+}
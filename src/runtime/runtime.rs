@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    Str(String),
+    Boolean(bool),
+    Nil,
+}
+
+// A single local scope's variables, shared by reference so a function can
+// capture the scope chain that was active when it was declared and keep
+// seeing later mutations made through other references to the same scope.
+#[derive(Clone)]
+pub struct Scope(Rc<RefCell<HashMap<String, Value>>>);
+
+impl Scope {
+    fn new() -> Self {
+        Scope(Rc::new(RefCell::new(HashMap::new())))
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        self.0.borrow().get(name).cloned()
+    }
+
+    fn set(&self, name: String, value: Value) {
+        self.0.borrow_mut().insert(name, value);
+    }
+}
+
+pub struct Runtime {
+    global_memory: HashMap<String, Value>,  // Global variables
+    stack: Vec<Scope>,                      // Chain of local scopes, innermost last
+}
+
+impl Runtime {
+    pub fn new() -> Self {
+        Runtime {
+            global_memory: HashMap::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn enter_scope(&mut self) {
+        self.stack.push(Scope::new());  // Create a new local scope
+    }
+
+    pub fn exit_scope(&mut self) {
+        self.stack.pop();  // Remove the current local scope
+    }
+
+    pub fn in_local_scope(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    pub fn set_global(&mut self, name: String, value: Value) {
+        self.global_memory.insert(name, value);
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.global_memory.get(name).cloned()
+    }
+
+    pub fn set_local(&mut self, name: String, value: Value) {
+        if let Some(scope) = self.stack.last() {
+            scope.set(name, value);
+        }
+    }
+
+    pub fn get_local(&self, name: &str) -> Option<Value> {
+        self.stack.last().and_then(|scope| scope.get(name))
+    }
+
+    // Looks a variable up `depth` scopes out from the current one, as
+    // resolved by `Resolver`: 0 is the innermost local scope, climbing out
+    // one scope per unit of depth until it falls off the stack into globals.
+    pub fn get_at_depth(&self, depth: usize, name: &str) -> Option<Value> {
+        let frame_count = self.stack.len();
+        if depth >= frame_count {
+            self.global_memory.get(name).cloned()
+        } else {
+            self.stack[frame_count - depth - 1].get(name)
+        }
+    }
+
+    // Snapshots the scope chain that is active right now, so a function
+    // declared at this point can close over it. Cheap: each `Scope` is just
+    // an `Rc` clone, not a copy of the variables themselves.
+    pub fn capture_scope(&self) -> Vec<Scope> {
+        self.stack.clone()
+    }
+
+    // Swaps in a captured closure environment for the duration of a call,
+    // returning whatever chain was active before so the caller's scopes can
+    // be restored once the call returns.
+    pub fn swap_scope(&mut self, captured: Vec<Scope>) -> Vec<Scope> {
+        std::mem::replace(&mut self.stack, captured)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::lexer::lexer::Token; 
+use crate::error::error::{Error, ErrorKind};
+use crate::lexer::lexer::Token;
 use crate::parser::parser::ASTNode;
 use std::collections::HashMap;
 
@@ -8,6 +9,8 @@ pub enum Type {
     Integer,
     String,
     Boolean,
+    Nil,
+    Function(Vec<Type>, Box<Type>),
 }
 
 // Define `SymbolEntry` struct to hold information about each symbol
@@ -34,9 +37,13 @@ impl SymbolTable {
     }
 
     // Inserts a new symbol into the current scope
-    pub fn insert(&mut self, name: String, typ: Type) -> Result<(), String> {
+    pub fn insert(&mut self, name: String, typ: Type, line: usize, column: usize) -> Result<(), Error> {
         if self.symbols.contains_key(&name) {
-            Err(format!("Symbol '{}' is already defined", name))
+            Err(Error::new(
+                ErrorKind::TypeError(format!("Symbol '{}' is already defined", name)),
+                line,
+                column,
+            ))
         } else {
             let entry = SymbolEntry { name: name.clone(), typ };
             self.symbols.insert(name, entry);
@@ -59,6 +66,7 @@ impl SymbolTable {
     // Define the `SemanticAnalyzer` struct to handle type checking and scope management
 pub struct SemanticAnalyzer {
     scopes: Vec<SymbolTable>,  // Stack of scopes
+    current_fn_return: Option<Type>,  // Return type inferred for the function being checked
 }
 
 impl SemanticAnalyzer {
@@ -66,12 +74,15 @@ impl SemanticAnalyzer {
     pub fn new() -> Self {
         SemanticAnalyzer {
             scopes: vec![SymbolTable::new(None)],  // Start with a global scope
+            current_fn_return: None,
         }
     }
 
-    // Enter a new scope by pushing a new symbol table onto the stack
+    // Enter a new scope by pushing a new symbol table onto the stack.
+    // `lookup_variable` already walks the whole `scopes` stack itself, so the
+    // table doesn't need its own cloned copy of every parent scope.
     pub fn enter_scope(&mut self) {
-        self.scopes.push(SymbolTable::new(Some(Box::new(self.scopes.last().unwrap().clone()))));
+        self.scopes.push(SymbolTable::new(None));
     }
 
     // Exit the current scope by popping the top symbol table off the stack
@@ -80,11 +91,11 @@ impl SemanticAnalyzer {
     }
 
     // Declare a new variable in the current scope
-    pub fn declare_variable(&mut self, name: String, typ: Type) -> Result<(), String> {
+    pub fn declare_variable(&mut self, name: String, typ: Type, line: usize, column: usize) -> Result<(), Error> {
         self.scopes
             .last_mut()
             .unwrap()
-            .insert(name, typ)
+            .insert(name, typ, line, column)
     }
 
     // Look up a variable in the current or parent scopes
@@ -98,53 +109,114 @@ impl SemanticAnalyzer {
     }
 
     // Check the types of expressions and statements in the AST
-    pub fn check_types(&mut self, node: &ASTNode) -> Result<Type, String> {
+    pub fn check_types(&mut self, node: &ASTNode) -> Result<Type, Error> {
         match node {
+            // Handle the top-level program / a bare list of statements
+            ASTNode::Program(stmts) | ASTNode::StmtList(stmts) => {
+                let mut result = Type::Boolean;
+                for stmt in stmts {
+                    result = self.check_types(stmt)?;
+                }
+                Ok(result)
+            }
             // Handle binary operations like addition, subtraction, etc.
-            ASTNode::BinaryOp(left, token, right) => {
+            ASTNode::BinaryOp(left, token, right, line, column) => {
                 let left_type = self.check_types(left)?;
                 let right_type = self.check_types(right)?;
                 match token {
+                    Token::Plus if left_type == Type::String && right_type == Type::String => Ok(Type::String),
                     Token::Plus | Token::Minus | Token::Star | Token::Slash => {
                         if left_type == Type::Integer && right_type == Type::Integer {
                             Ok(Type::Integer)
                         } else {
-                            Err(format!("Type error: {:?} and {:?} are not compatible with {:?}", left_type, right_type, token))
+                            Err(Error::new(
+                                ErrorKind::TypeError(format!("{:?} and {:?} are not compatible with {:?}", left_type, right_type, token)),
+                                *line,
+                                *column,
+                            ))
                         }
                     }
-                    Token::Equal | Token::NotEqual | Token::LessThan | Token::GreaterThan => {
+                    Token::Equal
+                    | Token::NotEqual
+                    | Token::LessThan
+                    | Token::GreaterThan
+                    | Token::LessEqual
+                    | Token::GreaterEqual => {
                         if left_type == right_type {
                             Ok(Type::Boolean)  // Comparison operators result in a boolean
                         } else {
-                            Err(format!("Type error: {:?} and {:?} cannot be compared with {:?}", left_type, right_type, token))
+                            Err(Error::new(
+                                ErrorKind::TypeError(format!("{:?} and {:?} cannot be compared with {:?}", left_type, right_type, token)),
+                                *line,
+                                *column,
+                            ))
                         }
                     }
-                    _ => Err(format!("Unknown binary operator: {:?}", token)),
+                    _ => Err(Error::new(ErrorKind::TypeError(format!("unknown binary operator: {:?}", token)), *line, *column)),
+                }
+            }
+            // Handle logical and/or, requiring boolean operands
+            ASTNode::LogicalOp(left, token, right, line, column) => {
+                let left_type = self.check_types(left)?;
+                let right_type = self.check_types(right)?;
+                if left_type == Type::Boolean && right_type == Type::Boolean {
+                    Ok(Type::Boolean)
+                } else {
+                    Err(Error::new(
+                        ErrorKind::TypeError(format!(
+                            "{:?} requires boolean operands, found {:?} and {:?}",
+                            token, left_type, right_type
+                        )),
+                        *line,
+                        *column,
+                    ))
                 }
             }
             // Handle identifier nodes (variable usage)
-            ASTNode::Identifier(name) => {
+            ASTNode::Identifier(name, _, line, column) => {
                 if let Some(typ) = self.lookup_variable(name) {
                     Ok(typ.clone())
                 } else {
-                    Err(format!("Undeclared variable: {}", name))
+                    Err(Error::new(ErrorKind::UndeclaredVariable(name.clone()), *line, *column))
                 }
             }
             // Handle integer literals
             ASTNode::Integer(_) => Ok(Type::Integer),
             // Handle string literals
             ASTNode::StringLiteral(_) => Ok(Type::String),
+            // Handle boolean literals
+            ASTNode::BooleanLiteral(_) => Ok(Type::Boolean),
+            // Handle the nil literal
+            ASTNode::NilLiteral => Ok(Type::Nil),
+            // Handle unary operations: `!` needs/produces a boolean, `-` needs/produces an integer
+            ASTNode::UnaryOp(token, operand, line, column) => {
+                let operand_type = self.check_types(operand)?;
+                match token {
+                    Token::Bang if operand_type == Type::Boolean => Ok(Type::Boolean),
+                    Token::Minus if operand_type == Type::Integer => Ok(Type::Integer),
+                    Token::Bang | Token::Minus => Err(Error::new(
+                        ErrorKind::TypeError(format!("{:?} is not compatible with {:?}", token, operand_type)),
+                        *line,
+                        *column,
+                    )),
+                    _ => Err(Error::new(ErrorKind::TypeError(format!("unknown unary operator: {:?}", token)), *line, *column)),
+                }
+            }
             // Handle let statements (variable declaration)
-            ASTNode::LetStmt(name, expr) => {
+            ASTNode::LetStmt(name, expr, line, column) => {
                 let expr_type = self.check_types(expr)?;
-                self.declare_variable(name.clone(), expr_type.clone())?;
+                self.declare_variable(name.clone(), expr_type.clone(), *line, *column)?;
                 Ok(expr_type)
             }
             // Handle if statements
-            ASTNode::IfStmt(condition, then_branch, else_branch) => {
+            ASTNode::IfStmt(condition, then_branch, else_branch, line, column) => {
                 let cond_type = self.check_types(condition)?;
                 if cond_type != Type::Boolean {
-                    return Err("Condition of if statement must be boolean".to_string());
+                    return Err(Error::new(
+                        ErrorKind::TypeError("condition of if statement must be boolean".to_string()),
+                        *line,
+                        *column,
+                    ));
                 }
                 self.check_types(then_branch)?;
                 if let Some(else_branch) = else_branch {
@@ -153,18 +225,99 @@ impl SemanticAnalyzer {
                 Ok(Type::Boolean)  // The type of the entire if statement might depend on your language's semantics
             }
             // Handle while statements
-            ASTNode::WhileStmt(condition, body) => {
+            ASTNode::WhileStmt(condition, body, line, column) => {
                 let cond_type = self.check_types(condition)?;
                 if cond_type != Type::Boolean {
-                    return Err("Condition of while statement must be boolean".to_string());
+                    return Err(Error::new(
+                        ErrorKind::TypeError("condition of while statement must be boolean".to_string()),
+                        *line,
+                        *column,
+                    ));
                 }
                 self.check_types(body)?;
                 Ok(Type::Boolean)
             }
             // Handle return statements
-            ASTNode::ReturnStmt(expr) => {
+            ASTNode::ReturnStmt(expr, line, column) => {
                 let expr_type = self.check_types(expr)?;
-                Ok(expr_type)  // Return type needs to be checked against the function's declared return type
+                match &self.current_fn_return {
+                    Some(expected) if expected != &expr_type => {
+                        return Err(Error::new(
+                            ErrorKind::TypeError(format!(
+                                "return type mismatch: expected {:?}, found {:?}",
+                                expected, expr_type
+                            )),
+                            *line,
+                            *column,
+                        ));
+                    }
+                    Some(_) => {}
+                    None => self.current_fn_return = Some(expr_type.clone()),
+                }
+                Ok(expr_type)
+            }
+            // Handle function declarations
+            ASTNode::FnDecl(name, params, body, line, column) => {
+                let param_types: Vec<Type> = params.iter().map(|(_, typ)| typ.clone()).collect();
+
+                self.enter_scope();
+                for (param_name, param_type) in params {
+                    self.declare_variable(param_name.clone(), param_type.clone(), *line, *column)?;
+                }
+                let enclosing_return = self.current_fn_return.take();
+                self.check_types(body)?;
+                let return_type = self.current_fn_return.take().unwrap_or(Type::Boolean);
+                self.current_fn_return = enclosing_return;
+                self.exit_scope();
+
+                self.declare_variable(name.clone(), Type::Function(param_types, Box::new(return_type)), *line, *column)?;
+                Ok(Type::Boolean)
+            }
+            // Handle call expressions
+            ASTNode::Call(callee, args, line, column) => {
+                let callee_name = match &**callee {
+                    ASTNode::Identifier(name, _, _, _) => name.clone(),
+                    _ => return Err(Error::new(ErrorKind::TypeError("can only call named functions".to_string()), *line, *column)),
+                };
+                let fn_type = self
+                    .lookup_variable(&callee_name)
+                    .cloned()
+                    .ok_or_else(|| Error::new(ErrorKind::UndeclaredVariable(callee_name.clone()), *line, *column))?;
+                match fn_type {
+                    Type::Function(param_types, return_type) => {
+                        if param_types.len() != args.len() {
+                            return Err(Error::new(
+                                ErrorKind::TypeError(format!(
+                                    "function '{}' expects {} argument(s), found {}",
+                                    callee_name,
+                                    param_types.len(),
+                                    args.len()
+                                )),
+                                *line,
+                                *column,
+                            ));
+                        }
+                        for (arg, expected) in args.iter().zip(param_types.iter()) {
+                            let arg_type = self.check_types(arg)?;
+                            if &arg_type != expected {
+                                return Err(Error::new(
+                                    ErrorKind::TypeError(format!(
+                                        "argument to '{}' expected {:?}, found {:?}",
+                                        callee_name, expected, arg_type
+                                    )),
+                                    *line,
+                                    *column,
+                                ));
+                            }
+                        }
+                        Ok(*return_type)
+                    }
+                    other => Err(Error::new(
+                        ErrorKind::TypeError(format!("'{}' is not callable (found {:?})", callee_name, other)),
+                        *line,
+                        *column,
+                    )),
+                }
             }
             // Handle blocks of statements
             ASTNode::Block(statements) => {
@@ -179,8 +332,6 @@ impl SemanticAnalyzer {
             ASTNode::ExprStmt(expr) => {
                 self.check_types(expr)
             }
-            // Handle other cases as needed...
-            _ => Err(format!("Unknown AST node type: {:?}", node)),
         }
     }
 }
\ No newline at end of file